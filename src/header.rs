@@ -12,9 +12,9 @@ use rustc_span::symbol::Ident;
 use rustc_span::{BytePos, DUMMY_SP, Span};
 use tracing::debug;
 
-use crate::comment::combine_strs_with_missing_comments;
+use crate::comment::{combine_strs_with_missing_comments, contains_comment};
 use crate::rewrite::RewriteContext;
-use crate::shape::Shape;
+use crate::shape::{Indent, Shape};
 use crate::source_map::SpanUtils;
 use crate::utils::{mk_sp, rewrite_ident};
 
@@ -31,6 +31,30 @@ pub(crate) fn format_header(
     let Some(part) = parts.next() else {
         return String::new();
     };
+    let parts: Vec<HeaderPart> = parts.collect();
+
+    // Fast path: if there isn't a single comment anywhere in the header, we
+    // don't need to walk every part pair through
+    // `combine_strs_with_missing_comments`. Just collapse the whole header to
+    // single-space-separated tokens, the same whitespace-collapse technique
+    // used for `extern crate` rewriting.
+    if let Some(last) = parts.last() {
+        let full_span = mk_sp(part.span.lo(), last.span.hi());
+        let has_comment = context
+            .snippet_provider
+            .span_to_snippet(full_span)
+            .map_or(true, contains_comment);
+        if !has_comment {
+            let mut result = part.snippet.into_owned();
+            for part in &parts {
+                result.push(' ');
+                result.push_str(&part.snippet);
+            }
+            return result;
+        }
+    } else {
+        return part.snippet.into_owned();
+    }
 
     let mut result = part.snippet.into_owned();
     let mut span = part.span;
@@ -82,18 +106,60 @@ impl HeaderPart {
             ast::VisibilityKind::Inherited => Cow::from(""),
             ast::VisibilityKind::Restricted { ref path, .. } => {
                 let ast::Path { ref segments, .. } = **path;
-                let mut segments_iter =
-                    segments.iter().map(|seg| rewrite_ident(context, seg.ident));
+                let mut segments_iter = segments
+                    .iter()
+                    .map(|seg| (rewrite_ident(context, seg.ident).to_owned(), seg.ident.span));
                 if path.is_global() {
                     segments_iter
                         .next()
                         .expect("Non-global path in pub(restricted)?");
                 }
-                let is_keyword = |s: &str| s == "crate" || s == "self" || s == "super";
-                let path = segments_iter.collect::<Vec<_>>().join("::");
-                let in_str = if is_keyword(&path) { "" } else { "in " };
 
-                Cow::from(format!("pub({}{})", in_str, path))
+                // Itemize the path segments and stitch them back together with
+                // `combine_strs_with_missing_comments`, mirroring how
+                // `format_header` recovers comments between header parts, so
+                // comments between segments (and after `in`) survive.
+                let shape = Shape::legacy(usize::MAX, Indent::empty());
+                let (first, first_span) = segments_iter
+                    .next()
+                    .expect("pub(restricted) path has no segments");
+                let mut path = first;
+                let mut span = first_span;
+                for (seg, seg_span) in segments_iter {
+                    let comments_span = span.between(seg_span);
+                    // `combine_strs_with_missing_comments` only recovers
+                    // comments; the `::` separator between segments is ours
+                    // to supply, since the helper joins with plain whitespace.
+                    let has_comment = context
+                        .snippet_provider
+                        .span_to_snippet(comments_span)
+                        .map_or(true, contains_comment);
+                    path = if has_comment {
+                        path.push_str("::");
+                        combine_strs_with_missing_comments(
+                            context, &path, &seg, comments_span, shape, true,
+                        )
+                        .unwrap_or_else(|_| format!("{}{}", path, seg))
+                    } else {
+                        format!("{}::{}", path, seg)
+                    };
+                    span = seg_span;
+                }
+
+                let is_keyword = |s: &str| s == "crate" || s == "self" || s == "super";
+                if is_keyword(&path) {
+                    Cow::from(format!("pub({})", path))
+                } else {
+                    let in_lo = context.snippet_provider.span_before(vis.span, "in");
+                    let in_span = mk_sp(in_lo, in_lo + BytePos("in".len() as u32));
+                    let comments_span = in_span.between(first_span);
+                    let path = combine_strs_with_missing_comments(
+                        context, "in", &path, comments_span, shape, true,
+                    )
+                    .unwrap_or_else(|_| format!("in {}", path));
+
+                    Cow::from(format!("pub({})", path))
+                }
             }
         };
 
@@ -103,6 +169,46 @@ impl HeaderPart {
         }
     }
 
+    /// Builds the `extern` qualifier of a header, e.g. `extern` or `extern "C"`.
+    ///
+    /// `abi` is `None` when the source used a bare `extern` (the implicit
+    /// default ABI) and `Some` when an explicit ABI string literal was
+    /// written. `span` covers the whole qualifier, including any comment
+    /// sitting between the `extern` keyword and the string literal.
+    pub(crate) fn extern_abi(
+        context: &RewriteContext<'_>,
+        abi: Option<&ast::StrLit>,
+        span: Span,
+    ) -> Self {
+        let lo = context.snippet_provider.span_before(span, "extern");
+        let extern_span = mk_sp(lo, lo + BytePos("extern".len() as u32));
+
+        let Some(abi) = abi else {
+            return if context.config.force_explicit_abi() {
+                Self::new("extern \"C\"", extern_span)
+            } else {
+                Self::new("extern", extern_span)
+            };
+        };
+
+        // Normalize the ABI literal to canonical double-quoted form, dropping
+        // any raw-string or escape oddities the source may have used.
+        let quoted_abi = format!("\"{}\"", abi.symbol_unescaped);
+
+        let comments_span = extern_span.between(abi.span);
+        let snippet = combine_strs_with_missing_comments(
+            context,
+            "extern",
+            &quoted_abi,
+            comments_span,
+            Shape::legacy(usize::MAX, Indent::empty()),
+            true,
+        )
+        .unwrap_or_else(|_| format!("extern {}", quoted_abi));
+
+        Self::new(snippet, mk_sp(lo, abi.span.hi()))
+    }
+
     pub(crate) fn safety(safety: ast::Safety) -> Self {
         let (snippet, span) = match safety {
             ast::Safety::Unsafe(span) => ("unsafe", span),
@@ -0,0 +1,7 @@
+// rustfmt-force_explicit_abi: true
+
+extern fn bare_abi() {}
+
+extern "C" fn already_explicit() {}
+
+extern "system" fn already_explicit_non_c() {}
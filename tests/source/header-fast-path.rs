@@ -0,0 +1,9 @@
+pub(crate)
+
+
+unsafe fn foo() {}
+
+pub unsafe
+fn bar() {}
+
+pub /* keep me */ unsafe fn baz() {}
@@ -0,0 +1,11 @@
+// rustfmt-force_explicit_abi: false
+
+extern "C" fn c_abi() {}
+
+extern /* c */ "C" fn c_abi_with_comment() {}
+
+extern fn bare_abi() {}
+
+extern "system" fn system_abi() {}
+
+extern "C" fn raw_abi() {}
@@ -0,0 +1,7 @@
+pub(in a::b::c) fn foo() {}
+
+pub(in /* note */ crate::foo) fn bar() {}
+
+pub(crate) fn baz() {}
+
+pub(in self::qux) fn quux() {}